@@ -1,68 +1,410 @@
 //! RAII guard used to notify child tasks that the parent has been dropped.
 
+use std::any::Any;
 use std::future::Future;
+use std::pin::Pin;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use std::task::{Context, Poll};
+use tokio::sync::mpsc;
+use tokio::sync::oneshot;
 use tokio::sync::watch::{channel, Receiver, Sender};
+use tokio::task::{JoinError, JoinHandle};
+
+/// Callback invoked with a panicking scoped task's payload. See [`SpawnScope::on_panic`].
+type PanicHandler = Arc<dyn Fn(Box<dyn Any + Send>) + Send + Sync>;
 
-#[derive(Debug)]
 pub struct SpawnScope {
-    tx: Option<Sender<()>>,
+    tx: Arc<Mutex<Option<Sender<()>>>>,
     rx: Receiver<()>,
+    drain_tx: Arc<Mutex<Option<mpsc::Sender<()>>>>,
+    drain_rx: tokio::sync::Mutex<mpsc::Receiver<()>>,
+    parent: Option<SpawnScopeHandle>,
+    on_panic: Option<PanicHandler>,
+    cancel_on_panic: bool,
+    task_count: Arc<AtomicUsize>,
+    next_task_id: Arc<AtomicUsize>,
+}
+
+impl std::fmt::Debug for SpawnScope {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("SpawnScope")
+            .field("rx", &self.rx)
+            .field("parent", &self.parent)
+            .field("cancel_on_panic", &self.cancel_on_panic)
+            .field("len", &self.len())
+            .finish()
+    }
+}
+
+/// `tx`/`drain_tx` are `Arc`-shared with every handle and spawned task (so handles can clone
+/// drain senders and `cancel_on_panic` can close `tx` from a supervising task), which means
+/// dropping this struct alone does not
+/// drop the underlying `Sender`s once other clones are still alive. Close them explicitly here
+/// so dropping the scope always cancels its children, regardless of who else is holding a clone.
+impl Drop for SpawnScope {
+    fn drop(&mut self) {
+        self.close();
+    }
 }
 
 impl SpawnScope {
     pub fn new() -> Self {
+        Self::with_parent(None)
+    }
+
+    fn with_parent(parent: Option<SpawnScopeHandle>) -> Self {
         let (tx, rx) = channel(());
-        Self { tx: Some(tx), rx }
+        let (drain_tx, drain_rx) = mpsc::channel(1);
+        Self {
+            tx: Arc::new(Mutex::new(Some(tx))),
+            rx,
+            drain_tx: Arc::new(Mutex::new(Some(drain_tx))),
+            drain_rx: tokio::sync::Mutex::new(drain_rx),
+            parent,
+            on_panic: None,
+            cancel_on_panic: false,
+            task_count: Arc::new(AtomicUsize::new(0)),
+            next_task_id: Arc::new(AtomicUsize::new(0)),
+        }
+    }
+
+    /// Number of tasks currently supervised by this scope, spawned through it or any of its
+    /// handles.
+    pub fn len(&self) -> usize {
+        self.task_count.load(Ordering::SeqCst)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Registers a callback invoked with the captured payload whenever a task spawned through
+    /// this scope (or one of its handles) panics.
+    pub fn on_panic<H>(mut self, handler: H) -> Self
+    where H: Fn(Box<dyn Any + Send>) + Send + Sync + 'static
+    {
+        self.on_panic = Some(Arc::new(handler));
+        self
+    }
+
+    /// When set, a panicking scoped task cancels this whole scope instead of only itself,
+    /// shutting down its siblings too.
+    pub fn cancel_on_panic(mut self) -> Self {
+        self.cancel_on_panic = true;
+        self
     }
 
+    /// Note that the returned handle only holds the *source* the drain sender is cloned from,
+    /// not a live sender itself — otherwise a handle kept around for later use (the normal
+    /// pattern) would keep the scope's drain channel open forever, and `shutdown`/`wait_idle`
+    /// would never observe it going idle.
     pub fn handle(&self) -> SpawnScopeHandle {
         let rx = self.rx.clone();
-        SpawnScopeHandle(rx)
+        let drain_tx = self.drain_tx.clone();
+        let parent = self.parent.clone().map(Box::new);
+        SpawnScopeHandle {
+            rx,
+            drain_tx,
+            parent,
+            cancel_tx: self.tx.clone(),
+            on_panic: self.on_panic.clone(),
+            cancel_on_panic: self.cancel_on_panic,
+            task_count: self.task_count.clone(),
+            next_task_id: self.next_task_id.clone(),
+        }
+    }
+
+    fn drain_sender(&self) -> mpsc::Sender<()> {
+        clone_drain_sender(&self.drain_tx)
     }
 
-    pub fn spawn<F>(&self, future: F) 
+    pub fn spawn<F>(&self, future: F) -> ScopedTask<()>
     where F: Future<Output = ()> + Send + 'static
     {
         let mut handle = self.handle();
-        tokio::spawn(async move {
+        let (guard, id) = TaskGuard::new(self.drain_sender(), self.task_count.clone(), &self.next_task_id);
+        let inner = tokio::spawn(async move {
+            let _guard = guard;
             tokio::select! {
                 _ = handle.left() => {},
                 _ = future => {},
             }
         });
+        ScopedTask::new(supervise(inner, self.on_panic.clone(), self.cancel_on_panic, self.tx.clone()), id)
     }
 
     pub fn close(&mut self) {
-        self.tx.take();
+        self.tx.lock().unwrap().take();
+        self.drain_tx.lock().unwrap().take();
+    }
+
+    /// Spawns a task that yields its result back through the returned `oneshot::Receiver`.
+    ///
+    /// `Some(value)` is sent once `future` completes normally. If the scope is torn down first,
+    /// the sending half is simply dropped, so the receiver resolves to a `RecvError` instead.
+    pub fn spawn_with_output<F, T>(&self, future: F) -> oneshot::Receiver<Option<T>>
+    where F: Future<Output = T> + Send + 'static, T: Send + 'static
+    {
+        let mut handle = self.handle();
+        let (guard, _id) = TaskGuard::new(self.drain_sender(), self.task_count.clone(), &self.next_task_id);
+        let (tx, rx) = oneshot::channel();
+        let inner = tokio::spawn(async move {
+            let _guard = guard;
+            tokio::select! {
+                _ = handle.left() => {},
+                value = future => {
+                    tx.send(Some(value)).ok();
+                },
+            }
+        });
+        supervise(inner, self.on_panic.clone(), self.cancel_on_panic, self.tx.clone());
+        rx
+    }
+
+    /// Waits for every task spawned through this scope (or one of its handles) to finish,
+    /// without also cancelling them. Callers must stop the tasks themselves (e.g. they are
+    /// expected to complete on their own) or this will wait forever.
+    ///
+    /// Like [`SpawnScope::shutdown`], this is a one-shot terminal operation: it consumes the
+    /// scope, since draining permanently closes its own drain-channel sender.
+    pub async fn wait_idle(self) {
+        self.drain_tx.lock().unwrap().take();
+        let mut rx = self.drain_rx.lock().await;
+        while let Some(_) = rx.recv().await {}
+    }
+
+    /// Notifies every task spawned through this scope that it should stop, then blocks until
+    /// all of them have actually returned.
+    pub async fn shutdown(self) {
+        self.tx.lock().unwrap().take();
+        self.drain_tx.lock().unwrap().take();
+        let mut rx = self.drain_rx.lock().await;
+        while let Some(_) = rx.recv().await {}
     }
 }
 
-#[derive(Debug)]
-pub struct SpawnScopeHandle(Receiver<()>);
+pub struct SpawnScopeHandle {
+    rx: Receiver<()>,
+    drain_tx: Arc<Mutex<Option<mpsc::Sender<()>>>>,
+    parent: Option<Box<SpawnScopeHandle>>,
+    cancel_tx: Arc<Mutex<Option<Sender<()>>>>,
+    on_panic: Option<PanicHandler>,
+    cancel_on_panic: bool,
+    task_count: Arc<AtomicUsize>,
+    next_task_id: Arc<AtomicUsize>,
+}
+
+impl std::fmt::Debug for SpawnScopeHandle {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("SpawnScopeHandle")
+            .field("rx", &self.rx)
+            .field("parent", &self.parent)
+            .field("cancel_on_panic", &self.cancel_on_panic)
+            .finish()
+    }
+}
 
 impl Clone for SpawnScopeHandle {
     fn clone(&self) -> Self {
-        let rx = self.0.clone();
-        SpawnScopeHandle(rx)
+        SpawnScopeHandle {
+            rx: self.rx.clone(),
+            drain_tx: self.drain_tx.clone(),
+            parent: self.parent.clone(),
+            cancel_tx: self.cancel_tx.clone(),
+            on_panic: self.on_panic.clone(),
+            cancel_on_panic: self.cancel_on_panic,
+            task_count: self.task_count.clone(),
+            next_task_id: self.next_task_id.clone(),
+        }
     }
 }
 
 impl SpawnScopeHandle {
     pub async fn left(&mut self) {
-        while let Some(_) = self.0.recv().await {}
+        match self.parent.as_deref_mut() {
+            Some(parent) => {
+                tokio::select! {
+                    _ = drain_rx(&mut self.rx) => {},
+                    _ = Box::pin(parent.left()) => {},
+                }
+            }
+            None => drain_rx(&mut self.rx).await,
+        }
     }
 
+    /// Creates a sub-scope whose tasks are cancelled when either this child scope is closed
+    /// or the handle's own scope (or one of its ancestors) is dropped.
+    pub fn child_scope(&self) -> SpawnScope {
+        SpawnScope::with_parent(Some(self.clone()))
+    }
 
-    pub fn spawn<F>(&self, future: F) 
+    pub fn spawn<F>(&self, future: F) -> ScopedTask<()>
     where F: Future<Output = ()> + Send + 'static
     {
         let mut handle = self.clone();
-        tokio::spawn(async move {
+        let (guard, id) = TaskGuard::new(clone_drain_sender(&self.drain_tx), self.task_count.clone(), &self.next_task_id);
+        let inner = tokio::spawn(async move {
+            let _guard = guard;
             tokio::select! {
                 _ = handle.left() => {},
                 _ = future => {},
             }
         });
+        ScopedTask::new(supervise(inner, self.on_panic.clone(), self.cancel_on_panic, self.cancel_tx.clone()), id)
+    }
+
+    /// Spawns a task that yields its result back through the returned `oneshot::Receiver`.
+    ///
+    /// `Some(value)` is sent once `future` completes normally. If the scope is torn down first,
+    /// the sending half is simply dropped, so the receiver resolves to a `RecvError` instead.
+    pub fn spawn_with_output<F, T>(&self, future: F) -> oneshot::Receiver<Option<T>>
+    where F: Future<Output = T> + Send + 'static, T: Send + 'static
+    {
+        let mut handle = self.clone();
+        let (guard, _id) = TaskGuard::new(clone_drain_sender(&self.drain_tx), self.task_count.clone(), &self.next_task_id);
+        let (tx, rx) = oneshot::channel();
+        let inner = tokio::spawn(async move {
+            let _guard = guard;
+            tokio::select! {
+                _ = handle.left() => {},
+                value = future => {
+                    tx.send(Some(value)).ok();
+                },
+            }
+        });
+        supervise(inner, self.on_panic.clone(), self.cancel_on_panic, self.cancel_tx.clone());
+        rx
+    }
+}
+
+async fn drain_rx(rx: &mut Receiver<()>) {
+    while rx.changed().await.is_ok() {}
+}
+
+/// Clones a fresh drain-channel sender from its shared source, for a single spawn call. Callers
+/// must not hold onto the clone beyond the spawned task's lifetime (see [`TaskGuard`]), or the
+/// scope's drain channel never empties.
+fn clone_drain_sender(source: &Mutex<Option<mpsc::Sender<()>>>) -> mpsc::Sender<()> {
+    source
+        .lock()
+        .unwrap()
+        .clone()
+        .expect("spawn called on a scope that has already shut down")
+}
+
+/// A handle to a task spawned through a [`SpawnScope`] or [`SpawnScopeHandle`].
+///
+/// Awaiting it resolves once the task has returned, aborted or panicked. Dropping it detaches
+/// the task, which keeps running under the scope's usual cancellation rules.
+#[derive(Debug)]
+pub struct ScopedTask<T> {
+    inner: JoinHandle<T>,
+    id: usize,
+}
+
+impl<T> ScopedTask<T> {
+    fn new(inner: JoinHandle<T>, id: usize) -> Self {
+        Self { inner, id }
+    }
+
+    /// Aborts the task. This only affects this task; siblings spawned through the same scope
+    /// keep running.
+    pub fn abort(&self) {
+        self.inner.abort();
+    }
+
+    pub fn is_finished(&self) -> bool {
+        self.inner.is_finished()
+    }
+
+    /// The monotonic id assigned to this task by its scope when it was spawned.
+    pub fn id(&self) -> usize {
+        self.id
+    }
+}
+
+impl<T> Future for ScopedTask<T> {
+    type Output = Result<T, JoinError>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        Pin::new(&mut self.get_mut().inner).poll(cx)
+    }
+}
+
+/// Held by a spawned task for its whole lifetime; dropping it (on completion, abort or panic)
+/// releases this task's slot in the scope's drain channel and decrements the scope's live task
+/// count exactly once.
+#[derive(Debug)]
+struct TaskGuard(
+    // Never read directly; retained purely so dropping `TaskGuard` drops it too, closing this
+    // task's slot in the scope's drain channel.
+    #[allow(dead_code)] mpsc::Sender<()>,
+    Arc<AtomicUsize>,
+);
+
+impl TaskGuard {
+    /// Registers a new task with the scope: bumps `task_count`, hands back a fresh monotonic id
+    /// from `next_task_id`, and wraps `drain_tx` so the count is decremented on drop.
+    fn new(
+        drain_tx: mpsc::Sender<()>,
+        task_count: Arc<AtomicUsize>,
+        next_task_id: &AtomicUsize,
+    ) -> (Self, usize) {
+        task_count.fetch_add(1, Ordering::SeqCst);
+        let id = next_task_id.fetch_add(1, Ordering::SeqCst);
+        (TaskGuard(drain_tx, task_count), id)
+    }
+}
+
+impl Drop for TaskGuard {
+    fn drop(&mut self) {
+        self.1.fetch_sub(1, Ordering::SeqCst);
+    }
+}
+
+/// Wires up a scoped task's panic policy, if any is configured. With no `on_panic` handler and
+/// no `cancel_on_panic`, this is a no-op that just hands back `inner` unchanged.
+fn supervise(
+    inner: JoinHandle<()>,
+    on_panic: Option<PanicHandler>,
+    cancel_on_panic: bool,
+    cancel_tx: Arc<Mutex<Option<Sender<()>>>>,
+) -> JoinHandle<()> {
+    if on_panic.is_none() && !cancel_on_panic {
+        return inner;
+    }
+    tokio::spawn(async move {
+        let guarded = AbortOnDrop(inner);
+        if let Err(err) = guarded.await {
+            if err.is_panic() {
+                let payload = err.into_panic();
+                if let Some(handler) = &on_panic {
+                    handler(payload);
+                }
+                if cancel_on_panic {
+                    cancel_tx.lock().unwrap().take();
+                }
+            }
+        }
+    })
+}
+
+/// Aborts the wrapped join handle's task on drop, so cancelling the supervising task (e.g. via
+/// `ScopedTask::abort`) propagates down to the real task it is watching.
+struct AbortOnDrop<T>(JoinHandle<T>);
+
+impl<T> Drop for AbortOnDrop<T> {
+    fn drop(&mut self) {
+        self.0.abort();
+    }
+}
+
+impl<T> Future for AbortOnDrop<T> {
+    type Output = Result<T, JoinError>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        Pin::new(&mut self.get_mut().0).poll(cx)
     }
 }
 
@@ -70,7 +412,7 @@ impl SpawnScopeHandle {
 async fn test_drop() {
     use std::future::Future;
     use std::time::Duration;
-    use tokio::time::delay_for;
+    use tokio::time::sleep;
     let scope = SpawnScope::new();
 
     fn get_task(mut scope: SpawnScopeHandle) -> impl Future<Output = i32> {
@@ -81,7 +423,7 @@ async fn test_drop() {
                   _ = scope.left() => {
                     return n
                   }
-                  _ = delay_for(Duration::from_millis(50)) => {
+                  _ = sleep(Duration::from_millis(50)) => {
                     n = n + 1
                   }
                 }
@@ -93,7 +435,7 @@ async fn test_drop() {
     let t2 = tokio::spawn(get_task(scope.handle()));
     let t3 = tokio::spawn(get_task(scope.handle()));
 
-    delay_for(Duration::from_millis(100)).await;
+    sleep(Duration::from_millis(100)).await;
     drop(scope);
 
     let (v1, v2, v3) = tokio::try_join!(t1, t2, t3).unwrap();
@@ -105,7 +447,7 @@ async fn test_drop() {
 #[tokio::test]
 async fn test_spawn() {
     use tokio::sync::oneshot::*;
-    
+
     let (tx, rx) = channel();
 
     struct Guard(Option<Sender<()>>);
@@ -126,4 +468,295 @@ async fn test_spawn() {
     drop(scope);
 
     rx.await.unwrap();
-}
\ No newline at end of file
+}
+
+#[tokio::test]
+async fn test_shutdown_waits_for_children() {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+
+    let scope = SpawnScope::new();
+    let torn_down = Arc::new(AtomicUsize::new(0));
+
+    struct Guard(Arc<AtomicUsize>);
+    impl Drop for Guard {
+        fn drop(&mut self) {
+            self.0.fetch_add(1, Ordering::SeqCst);
+        }
+    }
+
+    for _ in 0..3 {
+        let g = Guard(torn_down.clone());
+        scope.spawn(async move {
+            futures::future::pending::<()>().await;
+            drop(g)
+        });
+    }
+
+    scope.shutdown().await;
+
+    assert_eq!(torn_down.load(Ordering::SeqCst), 3);
+}
+
+#[tokio::test]
+async fn test_shutdown_completes_with_unused_handle_alive() {
+    let scope = SpawnScope::new();
+    let _handle = scope.handle();
+
+    // Holding a handle for later use (the normal pattern) must not keep the drain channel open.
+    tokio::time::timeout(std::time::Duration::from_millis(500), scope.shutdown())
+        .await
+        .expect("scope.shutdown() should not be blocked by an unused, live handle");
+}
+
+#[tokio::test]
+async fn test_abort_single_task() {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+
+    let scope = SpawnScope::new();
+    let torn_down = Arc::new(AtomicUsize::new(0));
+
+    struct Guard(Arc<AtomicUsize>);
+    impl Drop for Guard {
+        fn drop(&mut self) {
+            self.0.fetch_add(1, Ordering::SeqCst);
+        }
+    }
+
+    let g1 = Guard(torn_down.clone());
+    let aborted = scope.spawn(async move {
+        futures::future::pending::<()>().await;
+        drop(g1)
+    });
+
+    let g2 = Guard(torn_down.clone());
+    let sibling = scope.spawn(async move {
+        futures::future::pending::<()>().await;
+        drop(g2)
+    });
+
+    aborted.abort();
+    let result = aborted.await;
+    assert!(result.unwrap_err().is_cancelled());
+    assert!(!sibling.is_finished());
+
+    scope.shutdown().await;
+    assert_eq!(torn_down.load(Ordering::SeqCst), 2);
+}
+
+#[tokio::test]
+async fn test_child_scope_cascades_from_parent() {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+
+    let parent = SpawnScope::new();
+    let child = parent.handle().child_scope();
+    let torn_down = Arc::new(AtomicUsize::new(0));
+
+    struct Guard(Arc<AtomicUsize>);
+    impl Drop for Guard {
+        fn drop(&mut self) {
+            self.0.fetch_add(1, Ordering::SeqCst);
+        }
+    }
+
+    let g = Guard(torn_down.clone());
+    child.spawn(async move {
+        futures::future::pending::<()>().await;
+        drop(g)
+    });
+
+    drop(parent);
+    child.wait_idle().await;
+
+    assert_eq!(torn_down.load(Ordering::SeqCst), 1);
+}
+
+#[tokio::test]
+async fn test_child_scope_independent_of_siblings() {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+
+    let parent = SpawnScope::new();
+    let child = parent.handle().child_scope();
+    let torn_down = Arc::new(AtomicUsize::new(0));
+
+    struct Guard(Arc<AtomicUsize>);
+    impl Drop for Guard {
+        fn drop(&mut self) {
+            self.0.fetch_add(1, Ordering::SeqCst);
+        }
+    }
+
+    let sibling = parent.spawn(async {
+        futures::future::pending::<()>().await;
+    });
+
+    let g = Guard(torn_down.clone());
+    child.spawn(async move {
+        futures::future::pending::<()>().await;
+        drop(g)
+    });
+
+    child.shutdown().await;
+    assert_eq!(torn_down.load(Ordering::SeqCst), 1);
+
+    // closing the child must not have touched the parent scope.
+    assert!(!sibling.is_finished());
+    parent.shutdown().await;
+}
+
+#[tokio::test]
+async fn test_parent_shutdown_completes_with_idle_child_alive() {
+    let parent = SpawnScope::new();
+    let _child = parent.handle().child_scope();
+
+    // The child scope is merely held alive (no tasks spawned through it); the parent must
+    // still be able to shut down without waiting on it.
+    tokio::time::timeout(std::time::Duration::from_millis(500), parent.shutdown())
+        .await
+        .expect("parent.shutdown() should not be blocked by a live, idle child scope");
+}
+
+#[tokio::test]
+async fn test_spawn_with_output_completes() {
+    let scope = SpawnScope::new();
+
+    let rx = scope.spawn_with_output(async { 42 });
+
+    assert_eq!(rx.await.unwrap(), Some(42));
+}
+
+#[tokio::test]
+async fn test_spawn_with_output_cancelled() {
+    let scope = SpawnScope::new();
+
+    let rx = scope.spawn_with_output(async {
+        futures::future::pending::<()>().await;
+        42
+    });
+
+    drop(scope);
+
+    assert!(rx.await.is_err());
+}
+
+#[tokio::test]
+async fn test_on_panic_invokes_handler() {
+    use std::sync::atomic::{AtomicBool, Ordering};
+    use std::sync::Arc;
+
+    let called = Arc::new(AtomicBool::new(false));
+    let scope = {
+        let called = called.clone();
+        SpawnScope::new().on_panic(move |_payload| {
+            called.store(true, Ordering::SeqCst);
+        })
+    };
+
+    let task = scope.spawn(async {
+        panic!("boom");
+    });
+
+    task.await.ok();
+
+    assert!(called.load(Ordering::SeqCst));
+}
+
+#[tokio::test]
+async fn test_cancel_on_panic_shuts_down_siblings() {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+
+    let scope = SpawnScope::new().cancel_on_panic();
+    let torn_down = Arc::new(AtomicUsize::new(0));
+
+    struct Guard(Arc<AtomicUsize>);
+    impl Drop for Guard {
+        fn drop(&mut self) {
+            self.0.fetch_add(1, Ordering::SeqCst);
+        }
+    }
+
+    let panicking = scope.spawn(async {
+        panic!("boom");
+    });
+
+    let g = Guard(torn_down.clone());
+    scope.spawn(async move {
+        futures::future::pending::<()>().await;
+        drop(g)
+    });
+
+    panicking.await.ok();
+    scope.wait_idle().await;
+
+    assert_eq!(torn_down.load(Ordering::SeqCst), 1);
+}
+
+#[tokio::test]
+async fn test_cancel_on_panic_shuts_down_siblings_for_spawn_with_output() {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+
+    let scope = SpawnScope::new().cancel_on_panic();
+    let torn_down = Arc::new(AtomicUsize::new(0));
+
+    struct Guard(Arc<AtomicUsize>);
+    impl Drop for Guard {
+        fn drop(&mut self) {
+            self.0.fetch_add(1, Ordering::SeqCst);
+        }
+    }
+
+    let panicking = scope.spawn_with_output(async {
+        panic!("boom");
+    });
+
+    let g = Guard(torn_down.clone());
+    scope.spawn(async move {
+        futures::future::pending::<()>().await;
+        drop(g)
+    });
+
+    assert!(panicking.await.is_err());
+    scope.wait_idle().await;
+
+    assert_eq!(torn_down.load(Ordering::SeqCst), 1);
+}
+
+#[tokio::test]
+async fn test_len_tracks_active_tasks() {
+    let scope = SpawnScope::new();
+    assert!(scope.is_empty());
+
+    let t1 = scope.spawn(async {
+        futures::future::pending::<()>().await;
+    });
+    let t2 = scope.spawn(async {
+        futures::future::pending::<()>().await;
+    });
+    assert_eq!(scope.len(), 2);
+
+    t1.abort();
+    t1.await.ok();
+    assert_eq!(scope.len(), 1);
+
+    t2.abort();
+    t2.await.ok();
+    assert_eq!(scope.len(), 0);
+    assert!(scope.is_empty());
+}
+
+#[tokio::test]
+async fn test_spawn_assigns_monotonic_ids() {
+    let scope = SpawnScope::new();
+
+    let t1 = scope.spawn(async {});
+    let t2 = scope.spawn(async {});
+
+    assert!(t2.id() > t1.id());
+
+    scope.shutdown().await;
+}